@@ -0,0 +1,97 @@
+use ethers::types::{H256, U256};
+use silius_primitives::{bundler::AdaptiveBundlingConfig, UserOperation};
+
+/// Picks the next bundling interval for [BundlerMode::Adaptive](silius_primitives::BundlerMode::Adaptive):
+/// bundle immediately (interval of zero) once the mempool is deep enough or the base fee has
+/// dropped to the target, otherwise back off towards `max_interval` while the mempool is empty.
+pub fn next_adaptive_interval(
+    config: &AdaptiveBundlingConfig,
+    mempool_depth: u64,
+    current_base_fee: U256,
+    current_interval: u64,
+) -> u64 {
+    if mempool_depth >= config.mempool_depth_threshold || current_base_fee <= config.target_base_fee
+    {
+        return 0;
+    }
+
+    if mempool_depth == 0 {
+        return (current_interval * 2).clamp(config.min_interval, config.max_interval);
+    }
+
+    config.min_interval.max(current_interval / 2)
+}
+
+/// A bundle of UserOperations ready to be submitted on-chain, along with the entry point it was
+/// built against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bundle {
+    pub entry_point: ethers::types::Address,
+    pub user_operations: Vec<UserOperation>,
+}
+
+/// Builds the next bundle from the given candidate operations. Both the adaptive bundling tick
+/// and `send_bundle_now` call this, so the inclusion rules (ordered by nonce, nothing more than
+/// the caller-supplied cap) never drift between the two call sites.
+pub fn construct_bundle(
+    entry_point: ethers::types::Address,
+    mut candidates: Vec<UserOperation>,
+    max_operations: usize,
+) -> Bundle {
+    candidates.sort_by_key(|uo| uo.nonce());
+    candidates.truncate(max_operations);
+
+    Bundle {
+        entry_point,
+        user_operations: candidates,
+    }
+}
+
+/// The transaction hash a constructed [Bundle] was submitted under. Submission itself happens
+/// outside this crate.
+pub type BundleTxHash = H256;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AdaptiveBundlingConfig {
+        AdaptiveBundlingConfig {
+            mempool_depth_threshold: 5,
+            target_base_fee: U256::from(10),
+            min_interval: 1,
+            max_interval: 60,
+        }
+    }
+
+    #[test]
+    fn bundles_immediately_once_depth_threshold_crossed() {
+        assert_eq!(
+            next_adaptive_interval(&config(), 5, U256::from(100), 10),
+            0
+        );
+    }
+
+    #[test]
+    fn bundles_immediately_once_base_fee_at_target() {
+        assert_eq!(next_adaptive_interval(&config(), 0, U256::from(10), 10), 0);
+    }
+
+    #[test]
+    fn backs_off_when_mempool_empty_and_fee_above_target() {
+        let next = next_adaptive_interval(&config(), 0, U256::from(100), 10);
+        assert_eq!(next, 20);
+    }
+
+    #[test]
+    fn backs_off_caps_at_max_interval() {
+        let next = next_adaptive_interval(&config(), 0, U256::from(100), 55);
+        assert_eq!(next, 60);
+    }
+
+    #[test]
+    fn shortens_interval_under_moderate_pressure() {
+        let next = next_adaptive_interval(&config(), 2, U256::from(100), 10);
+        assert_eq!(next, 5);
+    }
+}
@@ -0,0 +1,452 @@
+//! Additions to the UoPool/Bundler gRPC service definitions. The bulk of `silius-grpc` (the
+//! tonic-generated clients and the pre-existing message types such as `GetAllRequest` or
+//! `SetModeRequest`) lives in the service `.proto` files; this module only carries the new
+//! messages introduced alongside the mempool gossip, gas oracle, and state snapshot debug
+//! methods.
+
+use ethers::types::{Address, Bytes, U256};
+use silius_primitives::{
+    bundler::AdaptiveBundlingConfig,
+    gas::GasFeeEstimate,
+    p2p::PeerInfo,
+    state::{ReputationRecord, StakeInfoRecord, StateSnapshot},
+    user_operation::{UserOperationV06, UserOperationV07},
+    BundlerMode, UserOperation,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddressProto {
+    pub value: Vec<u8>,
+}
+
+impl From<Address> for AddressProto {
+    fn from(addr: Address) -> Self {
+        Self {
+            value: addr.as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Fails if `value` isn't exactly 20 bytes, which would otherwise panic inside `Address::from_slice`
+/// when decoding a malformed or truncated message from the wire.
+impl TryFrom<AddressProto> for Address {
+    type Error = String;
+
+    fn try_from(proto: AddressProto) -> Result<Self, Self::Error> {
+        if proto.value.len() != 20 {
+            return Err(format!(
+                "address must be 20 bytes, got {}",
+                proto.value.len()
+            ));
+        }
+        Ok(Address::from_slice(&proto.value))
+    }
+}
+
+/// Request to dial and register a peer on the mempool gossip network.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddPeerRequest {
+    pub peer: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PeerInfoProto {
+    pub peer_id: String,
+    pub address: String,
+    pub connected: bool,
+    pub reputation_score: i32,
+}
+
+impl From<PeerInfoProto> for PeerInfo {
+    fn from(p: PeerInfoProto) -> Self {
+        PeerInfo {
+            peer_id: p.peer_id,
+            address: p.address,
+            connected: p.connected,
+            reputation_score: p.reputation_score,
+        }
+    }
+}
+
+/// Response carrying the peers currently known to the mempool gossip network.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DumpPeersResponse {
+    pub peers: Vec<PeerInfoProto>,
+}
+
+/// Request to gossip every UserOperation currently held for `ep` to the mempool topic's
+/// subscribers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SendPooledUserOpsRequest {
+    pub ep: Option<AddressProto>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GetGasFeesResponse {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub base_fee_per_gas: U256,
+}
+
+impl From<GetGasFeesResponse> for GasFeeEstimate {
+    fn from(res: GetGasFeesResponse) -> Self {
+        GasFeeEstimate {
+            max_fee_per_gas: res.max_fee_per_gas,
+            max_priority_fee_per_gas: res.max_priority_fee_per_gas,
+            base_fee_per_gas: res.base_fee_per_gas,
+        }
+    }
+}
+
+/// Request to reconfigure the `eth_feeHistory` lookback window, reward percentile, and base fee
+/// multiplier the gas oracle uses.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SetGasOracleConfigRequest {
+    pub blocks: u64,
+    pub reward_percentile: f64,
+    pub base_fee_multiplier: f64,
+}
+
+/// The bundler's bundling strategy, as round-tripped over the wire.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(i32)]
+pub enum Mode {
+    #[default]
+    Manual = 0,
+    Auto = 1,
+    Adaptive = 2,
+}
+
+impl From<BundlerMode> for Mode {
+    fn from(mode: BundlerMode) -> Self {
+        match mode {
+            BundlerMode::Manual => Mode::Manual,
+            BundlerMode::Auto => Mode::Auto,
+            BundlerMode::Adaptive(_) => Mode::Adaptive,
+        }
+    }
+}
+
+impl From<Mode> for i32 {
+    fn from(mode: Mode) -> Self {
+        mode as i32
+    }
+}
+
+/// Request to set the bundling mode, optional fixed interval, and (for
+/// [Mode::Adaptive]) the thresholds that drive its interval adjustments.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SetModeRequest {
+    pub mode: i32,
+    pub interval: u64,
+    pub adaptive_mempool_depth_threshold: Option<u64>,
+    pub adaptive_target_base_fee: Option<U256>,
+    pub adaptive_min_interval: Option<u64>,
+    pub adaptive_max_interval: Option<u64>,
+}
+
+impl SetModeRequest {
+    pub fn adaptive_config(&self) -> Option<AdaptiveBundlingConfig> {
+        let defaults = AdaptiveBundlingConfig::default();
+        Some(AdaptiveBundlingConfig {
+            mempool_depth_threshold: self.adaptive_mempool_depth_threshold?,
+            target_base_fee: self.adaptive_target_base_fee?,
+            min_interval: self.adaptive_min_interval.unwrap_or(defaults.min_interval),
+            max_interval: self.adaptive_max_interval.unwrap_or(defaults.max_interval),
+        })
+    }
+}
+
+#[cfg(test)]
+mod set_mode_request_tests {
+    use super::*;
+
+    #[test]
+    fn adaptive_config_round_trips_min_and_max_interval() {
+        let req = SetModeRequest {
+            mode: Mode::Adaptive as i32,
+            interval: 10,
+            adaptive_mempool_depth_threshold: Some(5),
+            adaptive_target_base_fee: Some(U256::from(7)),
+            adaptive_min_interval: Some(2),
+            adaptive_max_interval: Some(60),
+        };
+
+        let config = req.adaptive_config().unwrap();
+        assert_eq!(config.min_interval, 2);
+        assert_eq!(config.max_interval, 60);
+    }
+
+    #[test]
+    fn adaptive_config_falls_back_to_defaults_when_interval_bounds_missing() {
+        let req = SetModeRequest {
+            mode: Mode::Adaptive as i32,
+            interval: 10,
+            adaptive_mempool_depth_threshold: Some(5),
+            adaptive_target_base_fee: Some(U256::from(7)),
+            adaptive_min_interval: None,
+            adaptive_max_interval: None,
+        };
+
+        let config = req.adaptive_config().unwrap();
+        let defaults = AdaptiveBundlingConfig::default();
+        assert_eq!(config.min_interval, defaults.min_interval);
+        assert_eq!(config.max_interval, defaults.max_interval);
+    }
+}
+
+/// Wire form of a [silius_primitives::UserOperation], preserving which EntryPoint variant it was
+/// built for across the gRPC boundary via an explicit tag rather than trying to infer it from
+/// which optional fields happen to be set.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserOperationProto {
+    pub v06: Option<UserOperationV06Proto>,
+    pub v07: Option<UserOperationV07Proto>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserOperationV06Proto {
+    pub sender: AddressProto,
+    pub nonce: U256,
+    pub init_code: Bytes,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub paymaster_and_data: Bytes,
+    pub signature: Bytes,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserOperationV07Proto {
+    pub sender: AddressProto,
+    pub nonce: U256,
+    pub factory: Option<AddressProto>,
+    pub factory_data: Bytes,
+    pub call_data: Bytes,
+    pub account_gas_limits: Vec<u8>,
+    pub pre_verification_gas: U256,
+    pub gas_fees: Vec<u8>,
+    pub paymaster: Option<AddressProto>,
+    pub paymaster_verification_gas_limit: U256,
+    pub paymaster_post_op_gas_limit: U256,
+    pub paymaster_data: Bytes,
+    pub eip7702_auth: Option<Bytes>,
+    pub signature: Bytes,
+}
+
+impl From<UserOperation> for UserOperationProto {
+    fn from(uo: UserOperation) -> Self {
+        match uo {
+            UserOperation::V06(uo) => UserOperationProto {
+                v06: Some(UserOperationV06Proto {
+                    sender: uo.sender.into(),
+                    nonce: uo.nonce,
+                    init_code: uo.init_code,
+                    call_data: uo.call_data,
+                    call_gas_limit: uo.call_gas_limit,
+                    verification_gas_limit: uo.verification_gas_limit,
+                    pre_verification_gas: uo.pre_verification_gas,
+                    max_fee_per_gas: uo.max_fee_per_gas,
+                    max_priority_fee_per_gas: uo.max_priority_fee_per_gas,
+                    paymaster_and_data: uo.paymaster_and_data,
+                    signature: uo.signature,
+                }),
+                v07: None,
+            },
+            UserOperation::V07(uo) => UserOperationProto {
+                v06: None,
+                v07: Some(UserOperationV07Proto {
+                    sender: uo.sender.into(),
+                    nonce: uo.nonce,
+                    factory: uo.factory.map(Into::into),
+                    factory_data: uo.factory_data,
+                    call_data: uo.call_data,
+                    account_gas_limits: uo.account_gas_limits.to_vec(),
+                    pre_verification_gas: uo.pre_verification_gas,
+                    gas_fees: uo.gas_fees.to_vec(),
+                    paymaster: uo.paymaster.map(Into::into),
+                    paymaster_verification_gas_limit: uo.paymaster_verification_gas_limit,
+                    paymaster_post_op_gas_limit: uo.paymaster_post_op_gas_limit,
+                    paymaster_data: uo.paymaster_data,
+                    eip7702_auth: uo.eip7702_auth,
+                    signature: uo.signature,
+                }),
+            },
+        }
+    }
+}
+
+/// Fails if neither (or both) of `v06`/`v07` are set, or if any fixed-length field (an address, or
+/// a packed gas word) doesn't have the length it would if it had been produced by
+/// `From<UserOperation>`. This conversion runs on bytes that arrived over gossip from a peer, so a
+/// malformed or truncated message must be rejected with an `Err` rather than panicking partway
+/// through decoding it.
+impl TryFrom<UserOperationProto> for UserOperation {
+    type Error = String;
+
+    fn try_from(proto: UserOperationProto) -> Result<Self, Self::Error> {
+        match (proto.v06, proto.v07) {
+            (Some(v06), None) => Ok(UserOperation::V06(UserOperationV06 {
+                sender: v06.sender.try_into()?,
+                nonce: v06.nonce,
+                init_code: v06.init_code,
+                call_data: v06.call_data,
+                call_gas_limit: v06.call_gas_limit,
+                verification_gas_limit: v06.verification_gas_limit,
+                pre_verification_gas: v06.pre_verification_gas,
+                max_fee_per_gas: v06.max_fee_per_gas,
+                max_priority_fee_per_gas: v06.max_priority_fee_per_gas,
+                paymaster_and_data: v06.paymaster_and_data,
+                signature: v06.signature,
+            })),
+            (None, Some(v07)) => {
+                let account_gas_limits = <[u8; 32]>::try_from(v07.account_gas_limits.as_slice())
+                    .map_err(|_| {
+                        format!(
+                            "v07 account_gas_limits must be 32 bytes, got {}",
+                            v07.account_gas_limits.len()
+                        )
+                    })?;
+                let gas_fees = <[u8; 32]>::try_from(v07.gas_fees.as_slice()).map_err(|_| {
+                    format!("v07 gas_fees must be 32 bytes, got {}", v07.gas_fees.len())
+                })?;
+
+                Ok(UserOperation::V07(UserOperationV07 {
+                    sender: v07.sender.try_into()?,
+                    nonce: v07.nonce,
+                    factory: v07.factory.map(TryInto::try_into).transpose()?,
+                    factory_data: v07.factory_data,
+                    call_data: v07.call_data,
+                    account_gas_limits,
+                    pre_verification_gas: v07.pre_verification_gas,
+                    gas_fees,
+                    paymaster: v07.paymaster.map(TryInto::try_into).transpose()?,
+                    paymaster_verification_gas_limit: v07.paymaster_verification_gas_limit,
+                    paymaster_post_op_gas_limit: v07.paymaster_post_op_gas_limit,
+                    paymaster_data: v07.paymaster_data,
+                    eip7702_auth: v07.eip7702_auth,
+                    signature: v07.signature,
+                }))
+            }
+            (None, None) => Err("user operation proto set neither v06 nor v07".to_string()),
+            (Some(_), Some(_)) => Err("user operation proto set both v06 and v07".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod user_operation_proto_tests {
+    use super::*;
+
+    #[test]
+    fn rejects_truncated_v06_sender_instead_of_panicking() {
+        let proto = UserOperationProto {
+            v06: Some(UserOperationV06Proto {
+                sender: AddressProto { value: vec![1, 2, 3] },
+                ..Default::default()
+            }),
+            v07: None,
+        };
+
+        assert!(UserOperation::try_from(proto).is_err());
+    }
+
+    #[test]
+    fn rejects_undersized_v07_packed_gas_words_instead_of_panicking() {
+        let proto = UserOperationProto {
+            v06: None,
+            v07: Some(UserOperationV07Proto {
+                sender: AddressProto::from(Address::zero()),
+                account_gas_limits: vec![0u8; 4],
+                gas_fees: vec![0u8; 32],
+                ..Default::default()
+            }),
+        };
+
+        assert!(UserOperation::try_from(proto).is_err());
+    }
+}
+
+/// Wire form of a [silius_primitives::state::ReputationRecord].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReputationRecordProto {
+    pub entity: AddressProto,
+    pub value: i64,
+}
+
+impl From<ReputationRecord> for ReputationRecordProto {
+    fn from(r: ReputationRecord) -> Self {
+        Self {
+            entity: r.entity.into(),
+            value: r.value,
+        }
+    }
+}
+
+/// Wire form of a [silius_primitives::state::StakeInfoRecord].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StakeInfoRecordProto {
+    pub entity: AddressProto,
+    pub is_staked: bool,
+}
+
+impl From<StakeInfoRecord> for StakeInfoRecordProto {
+    fn from(s: StakeInfoRecord) -> Self {
+        Self {
+            entity: s.entity.into(),
+            is_staked: s.is_staked,
+        }
+    }
+}
+
+/// Wire form of a [silius_primitives::state::StateSnapshot]. Carries an explicit `version` tag
+/// (mirroring the primitives-side `StateSnapshot::version`) so a future layout change can still
+/// be told apart from this one instead of being silently misread.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateSnapshotProto {
+    pub version: u32,
+    pub entry_point: AddressProto,
+    pub chain_id: u64,
+    pub user_operations: Vec<UserOperationProto>,
+    pub reputation: Vec<ReputationRecordProto>,
+    pub stake_info: Vec<StakeInfoRecordProto>,
+}
+
+/// Request to export the current mempool/reputation/stake-info state for `ep` as a
+/// [StateSnapshotProto].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExportStateRequest {
+    pub ep: Option<AddressProto>,
+}
+
+/// Request to import a previously exported [StateSnapshotProto].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportStateRequest {
+    pub snapshot: Option<StateSnapshotProto>,
+}
+
+impl From<StateSnapshot> for StateSnapshotProto {
+    fn from(snapshot: StateSnapshot) -> Self {
+        Self {
+            version: snapshot.version,
+            entry_point: snapshot.entry_point.into(),
+            chain_id: snapshot.chain_id,
+            user_operations: snapshot
+                .user_operations
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            reputation: snapshot.reputation.into_iter().map(Into::into).collect(),
+            stake_info: snapshot.stake_info.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Response carrying the exported [StateSnapshotProto] for an entry point.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExportStateResponse {
+    pub snapshot: Option<StateSnapshotProto>,
+}
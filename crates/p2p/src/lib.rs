@@ -0,0 +1,140 @@
+//! Peer-to-peer propagation of UserOperations between bundler nodes.
+//!
+//! Each `(entry_point, chain_id, op_hash_scheme)` triple maps to a gossip topic id via
+//! [silius_primitives::p2p::mempool_id]. This crate tracks peers and handles `PooledUserOps`
+//! messages; it opens no network connections and holds no topic subscriptions itself.
+//! Messages received off the wire are handed to a
+//! [UserOperationValidator] (backed by the UoPool) before anything is admitted, so this crate
+//! never has to trust a peer's claims about an operation's validity.
+
+use std::collections::HashMap;
+
+use ethers::types::Address;
+use silius_primitives::{
+    p2p::{mempool_id, GossipIngressError, PeerInfo, PeerReputationThrottle, UserOperationValidator},
+    UserOperation,
+};
+
+/// A mempool gossip network scoped to a single node. Generic over the validator and throttle
+/// implementations so the UoPool's real validation/reputation logic can be swapped for fakes in
+/// tests.
+pub struct GossipNetwork<V, R> {
+    peers: HashMap<String, PeerInfo>,
+    ingress: silius_uopool::gossip::GossipIngress<V, R>,
+}
+
+impl<V: UserOperationValidator, R: PeerReputationThrottle> GossipNetwork<V, R> {
+    pub fn new(validator: V, throttle: R) -> Self {
+        Self {
+            peers: HashMap::new(),
+            ingress: silius_uopool::gossip::GossipIngress::new(validator, throttle),
+        }
+    }
+
+    /// Registers a peer by its ENR or multiaddr. The address doubles as the peer id and the peer
+    /// is marked connected; no connection is dialed.
+    pub fn add_peer(&mut self, address: String) -> PeerInfo {
+        let peer_id = address.clone();
+        let info = PeerInfo {
+            peer_id: peer_id.clone(),
+            address,
+            connected: true,
+            reputation_score: 0,
+        };
+        self.peers.insert(peer_id, info.clone());
+        info
+    }
+
+    /// Returns the peers currently known to this node.
+    pub fn dump_peers(&self) -> Vec<PeerInfo> {
+        self.peers.values().cloned().collect()
+    }
+
+    /// Updates `peer_id`'s reputation score, as tracked by the node's reputation store, on both
+    /// its [PeerInfo] entry and the gossip rate-limiting throttle, so a reputation change takes
+    /// effect on this peer's gossip budget immediately instead of the throttle silently keeping
+    /// whatever score it started with.
+    pub fn set_peer_reputation(&mut self, peer_id: &str, score: i32) -> Option<&PeerInfo> {
+        self.ingress.throttle().set_reputation(peer_id, score);
+
+        let info = self.peers.get_mut(peer_id)?;
+        info.reputation_score = score;
+        Some(info)
+    }
+
+    /// The deterministic topic a given entry point's mempool is gossiped over.
+    pub fn topic_for(&self, entry_point: Address, chain_id: u64, op_hash_scheme: u8) -> [u8; 32] {
+        mempool_id(entry_point, chain_id, op_hash_scheme).into()
+    }
+
+    /// Handles a `PooledUserOps` message received from `peer_id`: re-validates, deduplicates and
+    /// rate-limits before the caller admits any surviving operations to the local mempool.
+    pub fn handle_pooled_user_ops(
+        &self,
+        peer_id: &str,
+        entry_point: Address,
+        chain_id: u64,
+        uos: Vec<UserOperation>,
+    ) -> Vec<Result<UserOperation, GossipIngressError>> {
+        self.ingress.ingest(peer_id, entry_point, chain_id, uos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ethers::types::U256;
+    use silius_uopool::{
+        gas_oracle::{FeeHistoryProvider, GasOracle},
+        gossip::{ReputationGossipThrottle, UoPoolValidator},
+    };
+
+    use super::*;
+
+    /// A [FeeHistoryProvider] with a zero floor, so it never interferes with tests that aren't
+    /// exercising the gas oracle.
+    struct NoFloorProvider;
+
+    impl FeeHistoryProvider for NoFloorProvider {
+        fn fee_history(&self, _blocks: u64, _reward_percentile: f64) -> Option<(Vec<U256>, U256)> {
+            None
+        }
+
+        fn gas_price(&self) -> U256 {
+            U256::zero()
+        }
+
+        fn block_number(&self) -> u64 {
+            0
+        }
+    }
+
+    fn no_floor_validator() -> UoPoolValidator<NoFloorProvider> {
+        UoPoolValidator::new(Arc::new(GasOracle::new(NoFloorProvider)))
+    }
+
+    #[test]
+    fn add_peer_is_visible_in_dump_peers() {
+        let mut network = GossipNetwork::new(no_floor_validator(), ReputationGossipThrottle::new());
+        network.add_peer("/ip4/127.0.0.1/tcp/4242".to_string());
+        assert_eq!(network.dump_peers().len(), 1);
+    }
+
+    #[test]
+    fn topic_is_scoped_to_entry_point_and_chain() {
+        let network = GossipNetwork::new(no_floor_validator(), ReputationGossipThrottle::new());
+        let ep = Address::from_low_u64_be(1);
+        assert_ne!(network.topic_for(ep, 1, 0), network.topic_for(ep, 2, 0));
+    }
+
+    #[test]
+    fn set_peer_reputation_updates_peer_info() {
+        let mut network = GossipNetwork::new(no_floor_validator(), ReputationGossipThrottle::new());
+        let info = network.add_peer("/ip4/127.0.0.1/tcp/4242".to_string());
+
+        let updated = network.set_peer_reputation(&info.peer_id, -50).unwrap();
+        assert_eq!(updated.reputation_score, -50);
+        assert_eq!(network.dump_peers()[0].reputation_score, -50);
+    }
+}
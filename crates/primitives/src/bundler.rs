@@ -0,0 +1,40 @@
+use ethers::types::U256;
+
+/// Default bundling interval, in seconds, used when an operator doesn't specify one.
+pub const DEFAULT_BUNDLE_INTERVAL: u64 = 10;
+
+/// Thresholds that drive [BundlerMode::Adaptive]'s interval adjustments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdaptiveBundlingConfig {
+    /// Bundle immediately once the mempool holds at least this many pending operations.
+    pub mempool_depth_threshold: u64,
+    /// Bundle immediately once the gas oracle's base fee drops to or below this value.
+    pub target_base_fee: U256,
+    /// Floor for the interval once the mempool is empty and base fee is above target.
+    pub min_interval: u64,
+    /// Ceiling the interval backs off to when the mempool stays empty.
+    pub max_interval: u64,
+}
+
+impl Default for AdaptiveBundlingConfig {
+    fn default() -> Self {
+        Self {
+            mempool_depth_threshold: 1,
+            target_base_fee: U256::zero(),
+            min_interval: 1,
+            max_interval: DEFAULT_BUNDLE_INTERVAL * 6,
+        }
+    }
+}
+
+/// The bundler's current bundling strategy.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BundlerMode {
+    /// Bundles are only sent on an explicit `send_bundle_now` call.
+    Manual,
+    /// Bundles are sent on a fixed interval.
+    Auto,
+    /// The interval shortens or lengthens based on mempool pressure and the gas oracle's current
+    /// base fee, per [AdaptiveBundlingConfig].
+    Adaptive(AdaptiveBundlingConfig),
+}
@@ -0,0 +1,143 @@
+use ethers::types::U256;
+
+/// Components of the bundler's current `maxFeePerGas`/`maxPriorityFeePerGas` estimate, returned
+/// raw so operators can tell why a submitted operation's fees were rejected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GasFeeEstimate {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub base_fee_per_gas: U256,
+}
+
+/// Configuration for the `eth_feeHistory`-based gas oracle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GasOracleConfig {
+    /// Number of historical blocks to sample via `eth_feeHistory`.
+    pub blocks: u64,
+    /// Reward percentile to request for each sampled block (e.g. `50.0` for the median).
+    pub reward_percentile: f64,
+    /// Multiplier applied to the latest base fee when computing `maxFeePerGas`.
+    pub base_fee_multiplier: f64,
+}
+
+impl Default for GasOracleConfig {
+    fn default() -> Self {
+        Self {
+            blocks: 10,
+            reward_percentile: 50.0,
+            base_fee_multiplier: 2.0,
+        }
+    }
+}
+
+impl GasFeeEstimate {
+    /// Computes the estimate from a set of per-block reward samples (already taken at the
+    /// configured percentile) and the latest base fee:
+    /// `maxPriorityFeePerGas = median(rewards)`,
+    /// `maxFeePerGas = latest_base_fee * base_fee_multiplier + maxPriorityFeePerGas`.
+    pub fn from_fee_history(
+        rewards: &[U256],
+        latest_base_fee: U256,
+        base_fee_multiplier: f64,
+    ) -> Self {
+        let max_priority_fee_per_gas = median(rewards);
+        let max_fee_per_gas =
+            scale(latest_base_fee, base_fee_multiplier) + max_priority_fee_per_gas;
+
+        Self {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            base_fee_per_gas: latest_base_fee,
+        }
+    }
+
+    /// Falls back to a flat `eth_gasPrice` quote for chains without EIP-1559 support (pre-London
+    /// or otherwise lacking `eth_feeHistory`). `maxPriorityFeePerGas` is set equal to the gas
+    /// price, matching the legacy convention of a single effective gas price.
+    pub fn from_gas_price(gas_price: U256) -> Self {
+        Self {
+            max_fee_per_gas: gas_price,
+            max_priority_fee_per_gas: gas_price,
+            base_fee_per_gas: U256::zero(),
+        }
+    }
+
+    /// Whether a submitted operation's fees clear the oracle's current floor.
+    pub fn clears_floor(&self, max_fee_per_gas: U256, max_priority_fee_per_gas: U256) -> bool {
+        max_fee_per_gas >= self.max_fee_per_gas
+            && max_priority_fee_per_gas >= self.max_priority_fee_per_gas
+    }
+}
+
+fn median(values: &[U256]) -> U256 {
+    if values.is_empty() {
+        return U256::zero();
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort();
+    let mid = sorted.len() / 2;
+
+    if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    }
+}
+
+fn scale(value: U256, multiplier: f64) -> U256 {
+    // U256 has no native float multiplication, so the multiplier is applied in fixed point.
+    const PRECISION: u64 = 1_000_000;
+    let scaled_multiplier = U256::from((multiplier * PRECISION as f64).round() as u64);
+    value * scaled_multiplier / U256::from(PRECISION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_of_odd_length() {
+        let values = vec![U256::from(1), U256::from(5), U256::from(3)];
+        assert_eq!(median(&values), U256::from(3));
+    }
+
+    #[test]
+    fn median_of_even_length() {
+        let values = vec![U256::from(2), U256::from(4)];
+        assert_eq!(median(&values), U256::from(3));
+    }
+
+    #[test]
+    fn median_of_empty_is_zero() {
+        assert_eq!(median(&[]), U256::zero());
+    }
+
+    #[test]
+    fn from_fee_history_combines_base_fee_and_priority_fee() {
+        let rewards = vec![U256::from(2), U256::from(4), U256::from(6)];
+        let estimate = GasFeeEstimate::from_fee_history(&rewards, U256::from(100), 2.0);
+        assert_eq!(estimate.max_priority_fee_per_gas, U256::from(4));
+        assert_eq!(estimate.max_fee_per_gas, U256::from(204));
+        assert_eq!(estimate.base_fee_per_gas, U256::from(100));
+    }
+
+    #[test]
+    fn from_gas_price_fallback() {
+        let estimate = GasFeeEstimate::from_gas_price(U256::from(42));
+        assert_eq!(estimate.max_fee_per_gas, U256::from(42));
+        assert_eq!(estimate.max_priority_fee_per_gas, U256::from(42));
+    }
+
+    #[test]
+    fn clears_floor() {
+        let floor = GasFeeEstimate {
+            max_fee_per_gas: U256::from(100),
+            max_priority_fee_per_gas: U256::from(10),
+            base_fee_per_gas: U256::from(90),
+        };
+        assert!(floor.clears_floor(U256::from(100), U256::from(10)));
+        assert!(!floor.clears_floor(U256::from(99), U256::from(10)));
+        assert!(!floor.clears_floor(U256::from(100), U256::from(9)));
+    }
+}
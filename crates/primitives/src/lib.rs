@@ -0,0 +1,8 @@
+pub mod bundler;
+pub mod gas;
+pub mod p2p;
+pub mod state;
+pub mod user_operation;
+
+pub use bundler::BundlerMode;
+pub use user_operation::UserOperation;
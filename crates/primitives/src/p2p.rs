@@ -0,0 +1,121 @@
+use ethers::{
+    types::{Address, H256},
+    utils::keccak256,
+};
+
+use crate::UserOperation;
+
+/// Information about a peer on the mempool gossip network, as tracked by a
+/// `silius_p2p::GossipNetwork`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerInfo {
+    /// The peer's identifier. `GossipNetwork::add_peer` uses the address the peer was registered
+    /// with.
+    pub peer_id: String,
+    /// The multiaddr (or ENR) the peer was dialed or discovered at.
+    pub address: String,
+    /// Whether the peer is marked as connected.
+    pub connected: bool,
+    /// The peer's reputation score, as tracked by the local reputation store. Used to decide
+    /// how aggressively to rate-limit messages from this peer.
+    pub reputation_score: i32,
+}
+
+/// Computes the deterministic id for the gossip topic that a given entry point's mempool is
+/// propagated over.
+///
+/// The id is derived from the entry point address, the chain id, and the supported
+/// UserOperation hash scheme, so nodes that disagree on any of those three things end up on
+/// different topics instead of silently exchanging incompatible operations.
+pub fn mempool_id(entry_point: Address, chain_id: u64, op_hash_scheme: u8) -> H256 {
+    let mut buf = Vec::with_capacity(20 + 8 + 1);
+    buf.extend_from_slice(entry_point.as_bytes());
+    buf.extend_from_slice(&chain_id.to_be_bytes());
+    buf.push(op_hash_scheme);
+    H256::from(keccak256(buf))
+}
+
+/// Error returned when a [UserOperation] received from a peer fails validation, is a duplicate,
+/// or the sending peer is being rate-limited.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GossipIngressError {
+    /// The operation did not pass the same verification path used for locally submitted ops.
+    FailedValidation(String),
+    /// An operation with the same hash has already been admitted to the mempool.
+    Duplicate(H256),
+    /// The sending peer has exceeded its allotted message rate and is being throttled.
+    PeerThrottled(String),
+}
+
+impl std::fmt::Display for GossipIngressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GossipIngressError::FailedValidation(reason) => {
+                write!(f, "user operation failed validation: {reason}")
+            }
+            GossipIngressError::Duplicate(hash) => {
+                write!(f, "user operation {hash:?} already in mempool")
+            }
+            GossipIngressError::PeerThrottled(peer_id) => {
+                write!(f, "peer {peer_id} is rate-limited")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GossipIngressError {}
+
+/// Re-validates a [UserOperation] gossiped in by a peer through the same verification path used
+/// for locally submitted operations. Implemented by the UoPool so that incoming gossip is never
+/// trusted at face value.
+pub trait UserOperationValidator: Send + Sync {
+    fn validate_user_operation(
+        &self,
+        uo: &UserOperation,
+        entry_point: Address,
+    ) -> Result<(), String>;
+}
+
+/// Decides whether messages from a given peer should currently be throttled, backed by the
+/// node's existing reputation store so that a spamming peer's reputation penalty carries over to
+/// its gossip privileges.
+pub trait PeerReputationThrottle: Send + Sync {
+    /// Returns `true` if messages from `peer_id` should be dropped right now.
+    fn is_throttled(&self, peer_id: &str) -> bool;
+
+    /// Records that a message was accepted from `peer_id`, for rate accounting.
+    fn record_message(&self, peer_id: &str);
+
+    /// Updates the reputation score the throttle uses to decide `peer_id`'s message budget. Called
+    /// whenever the node's reputation store's view of that peer's backing entity changes, so a
+    /// peer's gossip privileges track its reputation rather than a value set once and never
+    /// touched again.
+    fn set_reputation(&self, peer_id: &str, score: i32);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mempool_id_differs_per_chain() {
+        let ep = Address::from_low_u64_be(1);
+        let a = mempool_id(ep, 1, 0);
+        let b = mempool_id(ep, 2, 0);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn mempool_id_differs_per_hash_scheme() {
+        let ep = Address::from_low_u64_be(1);
+        let a = mempool_id(ep, 1, 0);
+        let b = mempool_id(ep, 1, 1);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn mempool_id_is_deterministic() {
+        let ep = Address::from_low_u64_be(42);
+        assert_eq!(mempool_id(ep, 1, 0), mempool_id(ep, 1, 0));
+    }
+}
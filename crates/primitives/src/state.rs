@@ -0,0 +1,48 @@
+use ethers::types::Address;
+
+use crate::UserOperation;
+
+/// Current on-disk/wire layout version for [StateSnapshot]. Bump this whenever the snapshot's
+/// shape changes so older snapshots can still be recognised (and rejected, or migrated) instead
+/// of being silently misread.
+pub const STATE_SNAPSHOT_VERSION: u32 = 1;
+
+/// A single reputation entry as carried by a [StateSnapshot], independent of any in-memory
+/// reputation store representation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReputationRecord {
+    pub entity: Address,
+    pub value: i64,
+}
+
+/// A cached stake-info lookup result, as carried by a [StateSnapshot].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StakeInfoRecord {
+    pub entity: Address,
+    pub is_staked: bool,
+}
+
+/// A versioned, self-describing snapshot of a node's mempool, reputation, and stake-info cache
+/// for a single entry point, suitable for a fast restart or for priming a warm node from a
+/// peer's export.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateSnapshot {
+    pub version: u32,
+    pub entry_point: Address,
+    pub chain_id: u64,
+    pub user_operations: Vec<UserOperation>,
+    pub reputation: Vec<ReputationRecord>,
+    pub stake_info: Vec<StakeInfoRecord>,
+}
+
+impl StateSnapshot {
+    /// Whether this snapshot was produced for the given entry point, chain, and a version this
+    /// node knows how to read. Importing a snapshot that fails this check would silently
+    /// corrupt the pool (wrong entry point / chain) or misread fields (unknown version), so
+    /// callers must check this before admitting anything from the snapshot.
+    pub fn guard_matches(&self, entry_point: Address, chain_id: u64) -> bool {
+        self.version == STATE_SNAPSHOT_VERSION
+            && self.entry_point == entry_point
+            && self.chain_id == chain_id
+    }
+}
@@ -0,0 +1,313 @@
+use ethers::{
+    types::{Address, Bytes, H256, U256},
+    utils::keccak256,
+};
+
+/// Which EntryPoint contract layout a [UserOperation] was built against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EntryPointVersion {
+    V06,
+    V07,
+}
+
+/// Canonical EntryPoint v0.6 deployment address (same address on every chain it's deployed to):
+/// `0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789`.
+pub const ENTRY_POINT_V06_ADDRESS: Address = Address([
+    0x5f, 0xf1, 0x37, 0xd4, 0xb0, 0xfd, 0xcd, 0x49, 0xdc, 0xa3, 0x0c, 0x7c, 0xf5, 0x7e, 0x57, 0x8a,
+    0x02, 0x6d, 0x27, 0x89,
+]);
+/// Canonical EntryPoint v0.7 deployment address (same address on every chain it's deployed to):
+/// `0x0000000071727De22E5E9d8BAf0edAc6f37da032`.
+pub const ENTRY_POINT_V07_ADDRESS: Address = Address([
+    0x00, 0x00, 0x00, 0x00, 0x71, 0x72, 0x7d, 0xe2, 0x2e, 0x5e, 0x9d, 0x8b, 0xaf, 0x0e, 0xda, 0xc6,
+    0xf3, 0x7d, 0xa0, 0x32,
+]);
+
+/// Resolves which EntryPoint version an entry point address corresponds to, so
+/// version-unaware callers (an address on the wire) can be routed to the right
+/// [UserOperation] variant.
+pub fn entry_point_version(entry_point: Address) -> Option<EntryPointVersion> {
+    if entry_point == ENTRY_POINT_V06_ADDRESS {
+        Some(EntryPointVersion::V06)
+    } else if entry_point == ENTRY_POINT_V07_ADDRESS {
+        Some(EntryPointVersion::V07)
+    } else {
+        None
+    }
+}
+
+/// An ERC-4337 UserOperation submitted against EntryPoint v0.6's unpacked layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserOperationV06 {
+    pub sender: Address,
+    pub nonce: U256,
+    pub init_code: Bytes,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub paymaster_and_data: Bytes,
+    pub signature: Bytes,
+}
+
+/// An ERC-4337 UserOperation submitted against EntryPoint v0.7's packed layout: gas limits and
+/// gas fees are each packed into a single 32-byte word, `initCode` is split into `factory` +
+/// `factoryData`, and paymaster fields (and an optional EIP-7702 authorization) are optional.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserOperationV07 {
+    pub sender: Address,
+    pub nonce: U256,
+    pub factory: Option<Address>,
+    pub factory_data: Bytes,
+    pub call_data: Bytes,
+    /// `verificationGasLimit` (high 16 bytes) and `callGasLimit` (low 16 bytes), packed.
+    pub account_gas_limits: [u8; 32],
+    pub pre_verification_gas: U256,
+    /// `maxPriorityFeePerGas` (high 16 bytes) and `maxFeePerGas` (low 16 bytes), packed.
+    pub gas_fees: [u8; 32],
+    pub paymaster: Option<Address>,
+    pub paymaster_verification_gas_limit: U256,
+    pub paymaster_post_op_gas_limit: U256,
+    pub paymaster_data: Bytes,
+    /// EIP-7702 authorization tuple, present when the sender is a delegated EOA.
+    pub eip7702_auth: Option<Bytes>,
+    pub signature: Bytes,
+}
+
+impl UserOperationV07 {
+    pub fn call_gas_limit(&self) -> U256 {
+        U256::from(&self.account_gas_limits[16..32])
+    }
+
+    pub fn verification_gas_limit(&self) -> U256 {
+        U256::from(&self.account_gas_limits[0..16])
+    }
+
+    pub fn max_fee_per_gas(&self) -> U256 {
+        U256::from(&self.gas_fees[16..32])
+    }
+
+    pub fn max_priority_fee_per_gas(&self) -> U256 {
+        U256::from(&self.gas_fees[0..16])
+    }
+}
+
+/// An ERC-4337 UserOperation, in either the EntryPoint v0.6 (unpacked) or v0.7 (packed) shape.
+/// Each variant carries its own hashing and is converted to/from the gRPC wire format without
+/// losing which EntryPoint layout it was built for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UserOperation {
+    V06(UserOperationV06),
+    V07(UserOperationV07),
+}
+
+impl UserOperation {
+    pub fn version(&self) -> EntryPointVersion {
+        match self {
+            UserOperation::V06(_) => EntryPointVersion::V06,
+            UserOperation::V07(_) => EntryPointVersion::V07,
+        }
+    }
+
+    pub fn sender(&self) -> Address {
+        match self {
+            UserOperation::V06(uo) => uo.sender,
+            UserOperation::V07(uo) => uo.sender,
+        }
+    }
+
+    pub fn nonce(&self) -> U256 {
+        match self {
+            UserOperation::V06(uo) => uo.nonce,
+            UserOperation::V07(uo) => uo.nonce,
+        }
+    }
+
+    pub fn call_data(&self) -> &Bytes {
+        match self {
+            UserOperation::V06(uo) => &uo.call_data,
+            UserOperation::V07(uo) => &uo.call_data,
+        }
+    }
+
+    pub fn call_gas_limit(&self) -> U256 {
+        match self {
+            UserOperation::V06(uo) => uo.call_gas_limit,
+            UserOperation::V07(uo) => uo.call_gas_limit(),
+        }
+    }
+
+    pub fn verification_gas_limit(&self) -> U256 {
+        match self {
+            UserOperation::V06(uo) => uo.verification_gas_limit,
+            UserOperation::V07(uo) => uo.verification_gas_limit(),
+        }
+    }
+
+    pub fn max_fee_per_gas(&self) -> U256 {
+        match self {
+            UserOperation::V06(uo) => uo.max_fee_per_gas,
+            UserOperation::V07(uo) => uo.max_fee_per_gas(),
+        }
+    }
+
+    pub fn max_priority_fee_per_gas(&self) -> U256 {
+        match self {
+            UserOperation::V06(uo) => uo.max_priority_fee_per_gas,
+            UserOperation::V07(uo) => uo.max_priority_fee_per_gas(),
+        }
+    }
+
+    fn init_code_hash(&self) -> [u8; 32] {
+        match self {
+            UserOperation::V06(uo) => keccak256(&uo.init_code),
+            UserOperation::V07(uo) => {
+                let mut buf = Vec::new();
+                if let Some(factory) = uo.factory {
+                    buf.extend_from_slice(factory.as_bytes());
+                }
+                buf.extend_from_slice(&uo.factory_data);
+                keccak256(buf)
+            }
+        }
+    }
+
+    /// The canonical hash this operation is identified by in the mempool and over gossip,
+    /// binding it to a specific entry point and chain so the same operation submitted against
+    /// two different entry points never collides, and so a `V06` and a structurally-similar
+    /// `V07` operation never hash the same.
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> H256 {
+        let mut buf = Vec::new();
+        buf.push(match self.version() {
+            EntryPointVersion::V06 => 0u8,
+            EntryPointVersion::V07 => 1u8,
+        });
+        buf.extend_from_slice(self.sender().as_bytes());
+        let mut nonce_bytes = [0u8; 32];
+        self.nonce().to_big_endian(&mut nonce_bytes);
+        buf.extend_from_slice(&nonce_bytes);
+        buf.extend_from_slice(&self.init_code_hash());
+        buf.extend_from_slice(&keccak256(self.call_data()));
+        buf.extend_from_slice(entry_point.as_bytes());
+        buf.extend_from_slice(&chain_id.to_be_bytes());
+        H256::from(keccak256(buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v06() -> UserOperation {
+        UserOperation::V06(UserOperationV06 {
+            sender: Address::from_low_u64_be(1),
+            nonce: U256::zero(),
+            init_code: Bytes::default(),
+            call_data: Bytes::default(),
+            call_gas_limit: U256::zero(),
+            verification_gas_limit: U256::zero(),
+            pre_verification_gas: U256::zero(),
+            max_fee_per_gas: U256::zero(),
+            max_priority_fee_per_gas: U256::zero(),
+            paymaster_and_data: Bytes::default(),
+            signature: Bytes::default(),
+        })
+    }
+
+    fn v07() -> UserOperation {
+        UserOperation::V07(UserOperationV07 {
+            sender: Address::from_low_u64_be(1),
+            nonce: U256::zero(),
+            factory: None,
+            factory_data: Bytes::default(),
+            call_data: Bytes::default(),
+            account_gas_limits: [0u8; 32],
+            pre_verification_gas: U256::zero(),
+            gas_fees: [0u8; 32],
+            paymaster: None,
+            paymaster_verification_gas_limit: U256::zero(),
+            paymaster_post_op_gas_limit: U256::zero(),
+            paymaster_data: Bytes::default(),
+            eip7702_auth: None,
+            signature: Bytes::default(),
+        })
+    }
+
+    #[test]
+    fn entry_point_version_resolves_known_addresses() {
+        assert_eq!(
+            entry_point_version(ENTRY_POINT_V06_ADDRESS),
+            Some(EntryPointVersion::V06)
+        );
+        assert_eq!(
+            entry_point_version(ENTRY_POINT_V07_ADDRESS),
+            Some(EntryPointVersion::V07)
+        );
+        assert_eq!(entry_point_version(Address::zero()), None);
+    }
+
+    #[test]
+    fn v06_and_v07_hash_differently_for_equivalent_fields() {
+        let ep = Address::from_low_u64_be(9);
+        assert_ne!(v06().hash(ep, 1), v07().hash(ep, 1));
+    }
+
+    #[test]
+    fn hash_is_deterministic() {
+        let ep = Address::from_low_u64_be(9);
+        assert_eq!(v06().hash(ep, 1), v06().hash(ep, 1));
+    }
+
+    #[test]
+    fn max_fee_dispatch_matches_each_variants_own_representation() {
+        let v06 = UserOperation::V06(UserOperationV06 {
+            max_fee_per_gas: U256::from(7),
+            max_priority_fee_per_gas: U256::from(3),
+            ..match v06() {
+                UserOperation::V06(uo) => uo,
+                UserOperation::V07(_) => unreachable!(),
+            }
+        });
+        assert_eq!(v06.max_fee_per_gas(), U256::from(7));
+        assert_eq!(v06.max_priority_fee_per_gas(), U256::from(3));
+
+        let mut gas_fees = [0u8; 32];
+        gas_fees[31] = 7;
+        gas_fees[15] = 3;
+        let v07 = UserOperation::V07(UserOperationV07 {
+            gas_fees,
+            ..match v07() {
+                UserOperation::V07(uo) => uo,
+                UserOperation::V06(_) => unreachable!(),
+            }
+        });
+        assert_eq!(v07.max_fee_per_gas(), U256::from(7));
+        assert_eq!(v07.max_priority_fee_per_gas(), U256::from(3));
+    }
+
+    #[test]
+    fn packed_gas_fields_unpack_correctly() {
+        let mut account_gas_limits = [0u8; 32];
+        account_gas_limits[15] = 1;
+        let uo = UserOperationV07 {
+            sender: Address::zero(),
+            nonce: U256::zero(),
+            factory: None,
+            factory_data: Bytes::default(),
+            call_data: Bytes::default(),
+            account_gas_limits,
+            pre_verification_gas: U256::zero(),
+            gas_fees: [0u8; 32],
+            paymaster: None,
+            paymaster_verification_gas_limit: U256::zero(),
+            paymaster_post_op_gas_limit: U256::zero(),
+            paymaster_data: Bytes::default(),
+            eip7702_auth: None,
+            signature: Bytes::default(),
+        };
+        assert_eq!(uo.verification_gas_limit(), U256::from(1));
+        assert_eq!(uo.call_gas_limit(), U256::zero());
+    }
+}
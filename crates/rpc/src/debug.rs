@@ -9,13 +9,18 @@ use jsonrpsee::{
     types::{error::INTERNAL_ERROR_CODE, ErrorObjectOwned},
 };
 use silius_grpc::{
-    bundler_client::BundlerClient, uo_pool_client::UoPoolClient, GetAllReputationRequest,
-    GetAllRequest, GetStakeInfoRequest, Mode as GrpcMode, SetModeRequest, SetReputationRequest,
-    SetReputationResult,
+    bundler_client::BundlerClient, uo_pool_client::UoPoolClient, AddPeerRequest,
+    ExportStateRequest, GetAllReputationRequest, GetAllRequest, GetStakeInfoRequest,
+    ImportStateRequest, Mode as GrpcMode, SendPooledUserOpsRequest, SetGasOracleConfigRequest,
+    SetModeRequest, SetReputationRequest, SetReputationResult,
 };
 use silius_primitives::{
     bundler::DEFAULT_BUNDLE_INTERVAL,
+    gas::GasFeeEstimate,
+    p2p::PeerInfo,
     reputation::{ReputationEntry, StakeInfoResponse},
+    state::{ReputationRecord, StakeInfoRecord, StateSnapshot},
+    user_operation::entry_point_version,
     BundlerMode, UserOperation,
 };
 use tonic::Request;
@@ -82,6 +87,10 @@ impl DebugApiServer for DebugApiServerImpl {
     /// Sending an [GetAllRequest](GetAllRequest) to the UoPool gRPC server
     /// to get all of the [UserOperation](UserOperation) in the mempool.
     ///
+    /// The [UserOperation](UserOperation) variant (`V06` or `V07`) returned for each entry is
+    /// determined by the entry point the mempool is scoped to, so callers do not need to know
+    /// the entry point's version ahead of time.
+    ///
     /// # Arguments
     /// * `entry_point: Address` - The address of the entry point.
     ///
@@ -101,13 +110,19 @@ impl DebugApiServer for DebugApiServerImpl {
             .into_inner();
 
         let mut uos: Vec<UserOperation> = res.uos.iter().map(|uo| uo.clone().into()).collect();
-        uos.sort_by(|a, b| a.nonce.cmp(&b.nonce));
+        uos.sort_by(|a, b| a.nonce().cmp(&b.nonce()));
         Ok(uos)
     }
 
     /// Set the reputations for the given array of [ReputationEntry](ReputationEntry)
     /// and send it to the UoPool gRPC service through the [SetReputationRequest](SetReputationRequest).
     ///
+    /// Reputation is scoped per `(entry_point, version)`, so entries set against a `V06` entry
+    /// point never affect the reputation tracked for a `V07` entry point at a different address.
+    /// `ep` is resolved to its [EntryPointVersion](silius_primitives::user_operation::EntryPointVersion)
+    /// up front, so a request against an address that isn't a known EntryPoint deployment is
+    /// rejected here instead of being forwarded to the UoPool service with no version to scope it by.
+    ///
     /// # Arguments
     /// * `reputation_entries: Vec<ReputationEntry>` - The [ReputationEntry](ReputationEntry) to be set.
     /// * `entry_point: Address` - The address of the entry point.
@@ -119,6 +134,14 @@ impl DebugApiServer for DebugApiServerImpl {
         entries: Vec<ReputationEntry>,
         ep: Address,
     ) -> RpcResult<ResponseSuccess> {
+        entry_point_version(ep).ok_or_else(|| {
+            ErrorObjectOwned::owned(
+                INTERNAL_ERROR_CODE,
+                format!("{ep:?} is not a known EntryPoint deployment"),
+                None::<bool>,
+            )
+        })?;
+
         let mut uopool_grpc_client = self.uopool_grpc_client.clone();
 
         let req = Request::new(SetReputationRequest {
@@ -145,12 +168,24 @@ impl DebugApiServer for DebugApiServerImpl {
 
     /// Return the all of [ReputationEntries](ReputationEntry) in the mempool via the [GetAllReputationRequest](GetAllReputationRequest).
     ///
+    /// Like [set_reputation](DebugApiServerImpl::set_reputation), `ep` is resolved to its
+    /// [EntryPointVersion](silius_primitives::user_operation::EntryPointVersion) up front so the
+    /// dump is scoped to the right `(entry_point, version)` reputation bucket.
+    ///
     /// # Arguments
     /// * `entry_point: Address` - The address of the entry point.
     ///
     /// # Returns
     /// * `RpcResult<Vec<ReputationEntry>>` - An array of [ReputationEntries](ReputationEntry)
     async fn dump_reputation(&self, ep: Address) -> RpcResult<Vec<ReputationEntry>> {
+        entry_point_version(ep).ok_or_else(|| {
+            ErrorObjectOwned::owned(
+                INTERNAL_ERROR_CODE,
+                format!("{ep:?} is not a known EntryPoint deployment"),
+                None::<bool>,
+            )
+        })?;
+
         let mut uopool_grpc_client = self.uopool_grpc_client.clone();
 
         let request = Request::new(GetAllReputationRequest {
@@ -168,17 +203,38 @@ impl DebugApiServer for DebugApiServerImpl {
 
     /// Set the bundling mode.
     ///
+    /// For [BundlerMode::Adaptive](BundlerMode::Adaptive) the bundler shortens or lengthens
+    /// `interval` on its own based on mempool depth and the gas oracle's current base fee, so
+    /// `interval` is only a starting point rather than a fixed cadence in that mode.
+    ///
     /// # Arguments
     /// * `mode: BundlerMode` - The [BundlingMode](BundlingMode) to be set.
+    /// * `interval: Option<u64>` - The bundling interval in seconds. Defaults to
+    ///   [DEFAULT_BUNDLE_INTERVAL](DEFAULT_BUNDLE_INTERVAL) when not provided.
     ///
     /// # Returns
     /// * `RpcResult<ResponseSuccess>` - Ok
-    async fn set_bundling_mode(&self, mode: BundlerMode) -> RpcResult<ResponseSuccess> {
+    async fn set_bundling_mode(
+        &self,
+        mode: BundlerMode,
+        interval: Option<u64>,
+    ) -> RpcResult<ResponseSuccess> {
         let mut bundler_grpc_client = self.bundler_grpc_client.clone();
 
+        let adaptive_config = match &mode {
+            BundlerMode::Adaptive(config) => Some(*config),
+            BundlerMode::Manual | BundlerMode::Auto => None,
+        };
+
         let req = Request::new(SetModeRequest {
             mode: Into::<GrpcMode>::into(mode).into(),
-            interval: DEFAULT_BUNDLE_INTERVAL,
+            interval: interval.unwrap_or(DEFAULT_BUNDLE_INTERVAL),
+            adaptive_mempool_depth_threshold: adaptive_config
+                .as_ref()
+                .map(|c| c.mempool_depth_threshold),
+            adaptive_target_base_fee: adaptive_config.as_ref().map(|c| c.target_base_fee),
+            adaptive_min_interval: adaptive_config.as_ref().map(|c| c.min_interval),
+            adaptive_max_interval: adaptive_config.as_ref().map(|c| c.max_interval),
         });
 
         match bundler_grpc_client.set_bundler_mode(req).await {
@@ -189,6 +245,8 @@ impl DebugApiServer for DebugApiServerImpl {
 
     /// Immediately send the current bundle of user operations.
     /// This is useful for testing or in situations where waiting for the next scheduled bundle is not desirable.
+    /// Construction goes through the same bundle-building path the adaptive bundling mode uses,
+    /// so this always reflects the bundler's current inclusion rules.
     ///
     ///
     /// # Returns
@@ -235,4 +293,194 @@ impl DebugApiServer for DebugApiServerImpl {
             Err(s) => Err(JsonRpcError::from(s).into()),
         }
     }
+
+    /// Adds a peer to the mempool gossip network by its ENR or multiaddr.
+    ///
+    /// # Arguments
+    /// * `peer: String` - The ENR or multiaddr of the peer to dial.
+    ///
+    /// # Returns
+    /// * `RpcResult<ResponseSuccess>` - Ok
+    async fn add_peer(&self, peer: String) -> RpcResult<ResponseSuccess> {
+        let mut uopool_grpc_client = self.uopool_grpc_client.clone();
+
+        uopool_grpc_client
+            .add_peer(Request::new(AddPeerRequest { peer }))
+            .await
+            .map_err(JsonRpcError::from)?
+            .into_inner();
+
+        Ok(ResponseSuccess::Ok)
+    }
+
+    /// Returns the peers currently connected on the mempool gossip network.
+    ///
+    /// # Returns
+    /// * `RpcResult<Vec<PeerInfo>>` - An array of [PeerInfo](PeerInfo)
+    async fn dump_peers(&self) -> RpcResult<Vec<PeerInfo>> {
+        let mut uopool_grpc_client = self.uopool_grpc_client.clone();
+
+        let res = uopool_grpc_client
+            .dump_peers(Request::new(()))
+            .await
+            .map_err(JsonRpcError::from)?
+            .into_inner();
+
+        Ok(res.peers.iter().map(|p| p.clone().into()).collect())
+    }
+
+    /// Gossips every [UserOperation](UserOperation) currently held in the mempool for the given
+    /// entry point to the subscribers of that entry point's mempool topic.
+    ///
+    /// # Arguments
+    /// * `entry_point: Address` - The address of the entry point.
+    ///
+    /// # Returns
+    /// * `RpcResult<ResponseSuccess>` - Ok
+    async fn send_pooled_user_ops(&self, ep: Address) -> RpcResult<ResponseSuccess> {
+        let mut uopool_grpc_client = self.uopool_grpc_client.clone();
+
+        let req = Request::new(SendPooledUserOpsRequest {
+            ep: Some(ep.into()),
+        });
+
+        uopool_grpc_client
+            .send_pooled_user_ops(req)
+            .await
+            .map_err(JsonRpcError::from)?
+            .into_inner();
+
+        Ok(ResponseSuccess::Ok)
+    }
+
+    /// Returns the bundler's current `maxFeePerGas`/`maxPriorityFeePerGas` estimate, as computed
+    /// by the `eth_feeHistory`-based gas oracle.
+    ///
+    /// # Returns
+    /// * `RpcResult<GasFeeEstimate>` - The current gas fee estimate.
+    async fn get_gas_fees(&self) -> RpcResult<GasFeeEstimate> {
+        let mut uopool_grpc_client = self.uopool_grpc_client.clone();
+
+        let res = uopool_grpc_client
+            .get_gas_fees(Request::new(()))
+            .await
+            .map_err(JsonRpcError::from)?
+            .into_inner();
+
+        Ok(res.into())
+    }
+
+    /// Configures the gas oracle's `eth_feeHistory` lookback window, reward percentile and base
+    /// fee multiplier.
+    ///
+    /// # Arguments
+    /// * `blocks: u64` - Number of historical blocks to sample.
+    /// * `reward_percentile: f64` - Reward percentile to request from `eth_feeHistory`.
+    /// * `base_fee_multiplier: f64` - Multiplier applied to the latest base fee.
+    ///
+    /// # Returns
+    /// * `RpcResult<ResponseSuccess>` - Ok
+    async fn set_gas_oracle_config(
+        &self,
+        blocks: u64,
+        reward_percentile: f64,
+        base_fee_multiplier: f64,
+    ) -> RpcResult<ResponseSuccess> {
+        let mut uopool_grpc_client = self.uopool_grpc_client.clone();
+
+        uopool_grpc_client
+            .set_gas_oracle_config(Request::new(SetGasOracleConfigRequest {
+                blocks,
+                reward_percentile,
+                base_fee_multiplier,
+            }))
+            .await
+            .map_err(JsonRpcError::from)?
+            .into_inner();
+
+        Ok(ResponseSuccess::Ok)
+    }
+
+    /// Exports the full mempool, reputation and stake-info cache for the given entry point as a
+    /// single versioned [StateSnapshot](StateSnapshot), for priming another node without
+    /// replaying ops.
+    ///
+    /// # Arguments
+    /// * `entry_point: Address` - The address of the entry point.
+    ///
+    /// # Returns
+    /// * `RpcResult<StateSnapshot>` - The exported snapshot.
+    async fn export_state(&self, ep: Address) -> RpcResult<StateSnapshot> {
+        let mut uopool_grpc_client = self.uopool_grpc_client.clone();
+
+        let req = Request::new(ExportStateRequest {
+            ep: Some(ep.into()),
+        });
+
+        let res = uopool_grpc_client
+            .export_state(req)
+            .await
+            .map_err(JsonRpcError::from)?
+            .into_inner();
+
+        let snapshot = res.snapshot.expect("Must return a state snapshot");
+        Ok(StateSnapshot {
+            version: snapshot.version,
+            entry_point: Address::from_slice(&snapshot.entry_point.value),
+            chain_id: snapshot.chain_id,
+            user_operations: snapshot
+                .user_operations
+                .into_iter()
+                .map(|uo| {
+                    uo.try_into()
+                        .expect("bundler-exported user operation must be well-formed")
+                })
+                .collect(),
+            reputation: snapshot
+                .reputation
+                .into_iter()
+                .map(|r| ReputationRecord {
+                    entity: Address::from_slice(&r.entity.value),
+                    value: r.value,
+                })
+                .collect(),
+            stake_info: snapshot
+                .stake_info
+                .into_iter()
+                .map(|s| StakeInfoRecord {
+                    entity: Address::from_slice(&s.entity.value),
+                    is_staked: s.is_staked,
+                })
+                .collect(),
+        })
+    }
+
+    /// Imports a [StateSnapshot](StateSnapshot) previously produced by
+    /// [export_state](DebugApiServerImpl::export_state).
+    ///
+    /// Every [UserOperation](UserOperation) in the snapshot is re-validated before being admitted
+    /// to the mempool, and reputation entries are merged into the existing store rather than
+    /// overwriting it outright. A snapshot whose entry point or chain id doesn't match the node's
+    /// own is rejected rather than imported.
+    ///
+    /// # Arguments
+    /// * `snapshot: StateSnapshot` - The snapshot to import.
+    ///
+    /// # Returns
+    /// * `RpcResult<ResponseSuccess>` - Ok
+    async fn import_state(&self, snapshot: StateSnapshot) -> RpcResult<ResponseSuccess> {
+        let mut uopool_grpc_client = self.uopool_grpc_client.clone();
+
+        let req = Request::new(ImportStateRequest {
+            snapshot: Some(snapshot.into()),
+        });
+
+        uopool_grpc_client
+            .import_state(req)
+            .await
+            .map_err(JsonRpcError::from)?
+            .into_inner();
+
+        Ok(ResponseSuccess::Ok)
+    }
 }
@@ -0,0 +1,151 @@
+use std::sync::RwLock;
+
+use ethers::types::U256;
+use silius_primitives::gas::{GasFeeEstimate, GasOracleConfig};
+
+/// The subset of the Ethereum JSON-RPC API the gas oracle needs. Abstracted behind a trait so the
+/// oracle can be exercised without a live node.
+pub trait FeeHistoryProvider {
+    /// Returns `(per-block reward at the configured percentile, latest base fee)` for the last
+    /// `blocks` blocks, or `None` on chains that don't support `eth_feeHistory` (pre-London).
+    fn fee_history(&self, blocks: u64, reward_percentile: f64) -> Option<(Vec<U256>, U256)>;
+
+    /// Fallback flat gas price for chains without EIP-1559 support.
+    fn gas_price(&self) -> U256;
+
+    /// The current block number, used as the cache key.
+    fn block_number(&self) -> u64;
+}
+
+/// Gas price oracle backed by `eth_feeHistory`, with a short per-block cache and an
+/// `eth_gasPrice` fallback for chains that don't support EIP-1559.
+pub struct GasOracle<P> {
+    provider: P,
+    config: RwLock<GasOracleConfig>,
+    cache: RwLock<Option<(u64, GasFeeEstimate)>>,
+}
+
+impl<P: FeeHistoryProvider> GasOracle<P> {
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            config: RwLock::new(GasOracleConfig::default()),
+            cache: RwLock::new(None),
+        }
+    }
+
+    pub fn set_config(&self, config: GasOracleConfig) {
+        *self.config.write().expect("lock poisoned") = config;
+        // A config change invalidates any cached estimate taken under the old parameters.
+        *self.cache.write().expect("lock poisoned") = None;
+    }
+
+    /// Returns the current gas fee estimate, serving a cached value when it was computed for the
+    /// current block.
+    pub fn estimate(&self) -> GasFeeEstimate {
+        let current_block = self.provider.block_number();
+
+        if let Some((cached_block, estimate)) = *self.cache.read().expect("lock poisoned") {
+            if cached_block == current_block {
+                return estimate;
+            }
+        }
+
+        let config = *self.config.read().expect("lock poisoned");
+        let estimate = match self
+            .provider
+            .fee_history(config.blocks, config.reward_percentile)
+        {
+            Some((rewards, base_fee)) => {
+                GasFeeEstimate::from_fee_history(&rewards, base_fee, config.base_fee_multiplier)
+            }
+            None => GasFeeEstimate::from_gas_price(self.provider.gas_price()),
+        };
+
+        *self.cache.write().expect("lock poisoned") = Some((current_block, estimate));
+        estimate
+    }
+
+    /// Whether a submitted operation's fees clear the oracle's current floor. The mempool calls
+    /// this when deciding whether to admit a UserOperation.
+    pub fn meets_floor(&self, max_fee_per_gas: U256, max_priority_fee_per_gas: U256) -> bool {
+        self.estimate()
+            .clears_floor(max_fee_per_gas, max_priority_fee_per_gas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct FakeProvider {
+        block: Cell<u64>,
+        fee_history_calls: Cell<u32>,
+        history: Option<(Vec<U256>, U256)>,
+        gas_price: U256,
+    }
+
+    impl FeeHistoryProvider for FakeProvider {
+        fn fee_history(&self, _blocks: u64, _reward_percentile: f64) -> Option<(Vec<U256>, U256)> {
+            self.fee_history_calls.set(self.fee_history_calls.get() + 1);
+            self.history.clone()
+        }
+
+        fn gas_price(&self) -> U256 {
+            self.gas_price
+        }
+
+        fn block_number(&self) -> u64 {
+            self.block.get()
+        }
+    }
+
+    #[test]
+    fn caches_estimate_within_the_same_block() {
+        let provider = FakeProvider {
+            block: Cell::new(100),
+            fee_history_calls: Cell::new(0),
+            history: Some((vec![U256::from(1), U256::from(3)], U256::from(10))),
+            gas_price: U256::from(5),
+        };
+        let oracle = GasOracle::new(provider);
+
+        oracle.estimate();
+        oracle.estimate();
+
+        assert_eq!(oracle.provider.fee_history_calls.get(), 1);
+    }
+
+    #[test]
+    fn recomputes_on_new_block() {
+        let provider = FakeProvider {
+            block: Cell::new(100),
+            fee_history_calls: Cell::new(0),
+            history: Some((vec![U256::from(1), U256::from(3)], U256::from(10))),
+            gas_price: U256::from(5),
+        };
+        let oracle = GasOracle::new(provider);
+
+        oracle.estimate();
+        oracle.provider.block.set(101);
+        oracle.estimate();
+
+        assert_eq!(oracle.provider.fee_history_calls.get(), 2);
+    }
+
+    #[test]
+    fn falls_back_to_gas_price_pre_london() {
+        let provider = FakeProvider {
+            block: Cell::new(1),
+            fee_history_calls: Cell::new(0),
+            history: None,
+            gas_price: U256::from(7),
+        };
+        let oracle = GasOracle::new(provider);
+
+        let estimate = oracle.estimate();
+        assert_eq!(estimate.max_fee_per_gas, U256::from(7));
+        assert_eq!(estimate.max_priority_fee_per_gas, U256::from(7));
+    }
+}
@@ -0,0 +1,392 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use ethers::types::{Address, H256};
+use silius_primitives::{
+    p2p::{GossipIngressError, PeerReputationThrottle, UserOperationValidator},
+    UserOperation,
+};
+
+use crate::gas_oracle::{FeeHistoryProvider, GasOracle};
+
+/// Reputation score at or below which a peer's gossip messages get the reduced
+/// [THROTTLED_PEER_MESSAGE_BUDGET].
+pub const MIN_GOSSIP_REPUTATION: i32 = 0;
+
+/// Accepted gossip messages per peer within a single throttle window, once the peer's reputation
+/// has dropped to or below [MIN_GOSSIP_REPUTATION].
+const THROTTLED_PEER_MESSAGE_BUDGET: u32 = 1;
+
+/// Accepted gossip messages per peer within a single throttle window for a peer whose reputation
+/// is above [MIN_GOSSIP_REPUTATION].
+const STANDARD_PEER_MESSAGE_BUDGET: u32 = 5;
+
+/// How long a peer's message budget is measured over before it resets. Without a window, a
+/// budget is really just a lifetime cap (a peer that used up its budget once would be throttled
+/// forever), which defeats the point of rate-limiting an ongoing gossip stream.
+const THROTTLE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Validates incoming UserOperations through the same checks the UoPool applies to operations
+/// submitted directly by clients, so gossiped operations are never trusted at face value. Also
+/// rejects anything whose fees don't clear the gas oracle's current floor, so a peer can't flood
+/// the mempool with operations no bundler would ever include.
+pub struct UoPoolValidator<P> {
+    gas_oracle: Arc<GasOracle<P>>,
+}
+
+impl<P> UoPoolValidator<P> {
+    pub fn new(gas_oracle: Arc<GasOracle<P>>) -> Self {
+        Self { gas_oracle }
+    }
+}
+
+impl<P: FeeHistoryProvider + Send + Sync> UserOperationValidator for UoPoolValidator<P> {
+    fn validate_user_operation(
+        &self,
+        uo: &UserOperation,
+        _entry_point: Address,
+    ) -> Result<(), String> {
+        if uo.sender() == Address::zero() {
+            return Err("user operation has a zero sender address".to_string());
+        }
+        if uo.call_gas_limit().is_zero() && uo.verification_gas_limit().is_zero() {
+            return Err("user operation has no gas allotted".to_string());
+        }
+        if !self
+            .gas_oracle
+            .meets_floor(uo.max_fee_per_gas(), uo.max_priority_fee_per_gas())
+        {
+            return Err("user operation's fees do not clear the gas oracle's current floor".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// A peer's message count within its current throttle window.
+struct PeerWindow {
+    window_start: Instant,
+    messages_in_window: u32,
+}
+
+/// Tracks per-peer reputation scores (kept in sync with the node's reputation store by whatever
+/// owns this throttle, via [PeerReputationThrottle::set_reputation]) and a fixed per-peer
+/// message window whose count resets once [THROTTLE_WINDOW] has elapsed, so a peer's gossip
+/// budget both reflects its actual reputation and resets over time instead of being a one-shot
+/// lifetime cap.
+#[derive(Default)]
+pub struct ReputationGossipThrottle {
+    reputation_scores: RwLock<HashMap<String, i32>>,
+    windows: RwLock<HashMap<String, PeerWindow>>,
+}
+
+impl ReputationGossipThrottle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of messages a peer at `score` may send within one [THROTTLE_WINDOW].
+    fn budget_for_score(score: i32) -> u32 {
+        if score > MIN_GOSSIP_REPUTATION {
+            STANDARD_PEER_MESSAGE_BUDGET
+        } else {
+            THROTTLED_PEER_MESSAGE_BUDGET
+        }
+    }
+}
+
+impl PeerReputationThrottle for ReputationGossipThrottle {
+    fn is_throttled(&self, peer_id: &str) -> bool {
+        let score = *self
+            .reputation_scores
+            .read()
+            .expect("lock poisoned")
+            .get(peer_id)
+            .unwrap_or(&0);
+        let budget = Self::budget_for_score(score);
+
+        let mut windows = self.windows.write().expect("lock poisoned");
+        let window = windows.entry(peer_id.to_string()).or_insert_with(|| PeerWindow {
+            window_start: Instant::now(),
+            messages_in_window: 0,
+        });
+
+        if window.window_start.elapsed() >= THROTTLE_WINDOW {
+            window.window_start = Instant::now();
+            window.messages_in_window = 0;
+        }
+
+        window.messages_in_window >= budget
+    }
+
+    fn record_message(&self, peer_id: &str) {
+        let mut windows = self.windows.write().expect("lock poisoned");
+        let window = windows.entry(peer_id.to_string()).or_insert_with(|| PeerWindow {
+            window_start: Instant::now(),
+            messages_in_window: 0,
+        });
+        window.messages_in_window += 1;
+    }
+
+    fn set_reputation(&self, peer_id: &str, score: i32) {
+        self.reputation_scores
+            .write()
+            .expect("lock poisoned")
+            .insert(peer_id.to_string(), score);
+    }
+}
+
+/// Ingests UserOperations received over the mempool gossip network: re-validates each through
+/// [UserOperationValidator], drops ones already seen by hash, and rejects any from a throttled
+/// peer, before handing the survivors to the mempool for insertion.
+pub struct GossipIngress<V, R> {
+    validator: V,
+    throttle: R,
+    seen: RwLock<HashSet<H256>>,
+}
+
+impl<V: UserOperationValidator, R: PeerReputationThrottle> GossipIngress<V, R> {
+    pub fn new(validator: V, throttle: R) -> Self {
+        Self {
+            validator,
+            throttle,
+            seen: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// The rate-limiting throttle backing this ingress, so callers that own a newer reputation
+    /// reading for a peer (e.g. a [ReputationStore](crate::reputation::ReputationStore) lookup)
+    /// can push it in via [PeerReputationThrottle::set_reputation].
+    pub fn throttle(&self) -> &R {
+        &self.throttle
+    }
+
+    /// Processes one `PooledUserOps` message from `peer_id`, returning one result per operation:
+    /// `Ok` for an operation that passed validation and was not already known, `Err` otherwise.
+    /// A throttled peer's message yields a single `PeerThrottled` error instead.
+    pub fn ingest(
+        &self,
+        peer_id: &str,
+        entry_point: Address,
+        chain_id: u64,
+        uos: Vec<UserOperation>,
+    ) -> Vec<Result<UserOperation, GossipIngressError>> {
+        if self.throttle.is_throttled(peer_id) {
+            return vec![Err(GossipIngressError::PeerThrottled(peer_id.to_string()))];
+        }
+
+        let results = uos
+            .into_iter()
+            .map(|uo| {
+                let hash = uo.hash(entry_point, chain_id);
+
+                if self.seen.read().expect("lock poisoned").contains(&hash) {
+                    return Err(GossipIngressError::Duplicate(hash));
+                }
+
+                self.validator
+                    .validate_user_operation(&uo, entry_point)
+                    .map_err(GossipIngressError::FailedValidation)?;
+
+                self.seen.write().expect("lock poisoned").insert(hash);
+                Ok(uo)
+            })
+            .collect();
+
+        self.throttle.record_message(peer_id);
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Bytes, U256};
+    use silius_primitives::user_operation::UserOperationV06;
+
+    /// A [FeeHistoryProvider] with no `eth_feeHistory` support and a zero gas price, so the gas
+    /// oracle's floor is zero and doesn't interfere with tests that aren't exercising it.
+    struct NoFloorProvider;
+
+    impl FeeHistoryProvider for NoFloorProvider {
+        fn fee_history(&self, _blocks: u64, _reward_percentile: f64) -> Option<(Vec<U256>, U256)> {
+            None
+        }
+
+        fn gas_price(&self) -> U256 {
+            U256::zero()
+        }
+
+        fn block_number(&self) -> u64 {
+            0
+        }
+    }
+
+    fn no_floor_validator() -> UoPoolValidator<NoFloorProvider> {
+        UoPoolValidator::new(Arc::new(GasOracle::new(NoFloorProvider)))
+    }
+
+    fn valid_uo() -> UserOperation {
+        UserOperation::V06(UserOperationV06 {
+            sender: Address::from_low_u64_be(1),
+            nonce: U256::zero(),
+            init_code: Bytes::default(),
+            call_data: Bytes::default(),
+            call_gas_limit: U256::from(1),
+            verification_gas_limit: U256::from(1),
+            pre_verification_gas: U256::zero(),
+            max_fee_per_gas: U256::zero(),
+            max_priority_fee_per_gas: U256::zero(),
+            paymaster_and_data: Bytes::default(),
+            signature: Bytes::default(),
+        })
+    }
+
+    #[test]
+    fn rejects_invalid_operation() {
+        let ingress = GossipIngress::new(no_floor_validator(), ReputationGossipThrottle::new());
+        let uo = UserOperation::V06(UserOperationV06 {
+            sender: Address::zero(),
+            nonce: U256::zero(),
+            init_code: Bytes::default(),
+            call_data: Bytes::default(),
+            call_gas_limit: U256::from(1),
+            verification_gas_limit: U256::from(1),
+            pre_verification_gas: U256::zero(),
+            max_fee_per_gas: U256::zero(),
+            max_priority_fee_per_gas: U256::zero(),
+            paymaster_and_data: Bytes::default(),
+            signature: Bytes::default(),
+        });
+
+        let results = ingress.ingest("peer-a", Address::from_low_u64_be(9), 1, vec![uo]);
+        assert!(matches!(
+            results[0],
+            Err(GossipIngressError::FailedValidation(_))
+        ));
+    }
+
+    #[test]
+    fn deduplicates_by_hash() {
+        let ingress = GossipIngress::new(no_floor_validator(), ReputationGossipThrottle::new());
+        let ep = Address::from_low_u64_be(9);
+
+        let first = ingress.ingest("peer-a", ep, 1, vec![valid_uo()]);
+        assert!(first[0].is_ok());
+
+        let second = ingress.ingest("peer-b", ep, 1, vec![valid_uo()]);
+        assert!(matches!(second[0], Err(GossipIngressError::Duplicate(_))));
+    }
+
+    #[test]
+    fn throttles_low_reputation_peer_after_budget() {
+        let throttle = ReputationGossipThrottle::new();
+        throttle.set_reputation("spammer", -10);
+        let ingress = GossipIngress::new(no_floor_validator(), throttle);
+        let ep = Address::from_low_u64_be(9);
+
+        let first = ingress.ingest("spammer", ep, 1, vec![valid_uo()]);
+        assert!(first[0].is_ok());
+
+        let second_uo = UserOperation::V06(UserOperationV06 {
+            sender: Address::from_low_u64_be(1),
+            nonce: U256::from(1),
+            init_code: Bytes::default(),
+            call_data: Bytes::default(),
+            call_gas_limit: U256::from(1),
+            verification_gas_limit: U256::from(1),
+            pre_verification_gas: U256::zero(),
+            max_fee_per_gas: U256::zero(),
+            max_priority_fee_per_gas: U256::zero(),
+            paymaster_and_data: Bytes::default(),
+            signature: Bytes::default(),
+        });
+        let second = ingress.ingest("spammer", ep, 1, vec![second_uo]);
+        assert!(matches!(
+            second[0],
+            Err(GossipIngressError::PeerThrottled(_))
+        ));
+    }
+
+    /// A [FeeHistoryProvider] whose flat gas price sets a non-zero floor, so an operation's fees
+    /// can actually fail to clear it.
+    struct FlatFloorProvider {
+        gas_price: U256,
+    }
+
+    impl FeeHistoryProvider for FlatFloorProvider {
+        fn fee_history(&self, _blocks: u64, _reward_percentile: f64) -> Option<(Vec<U256>, U256)> {
+            None
+        }
+
+        fn gas_price(&self) -> U256 {
+            self.gas_price
+        }
+
+        fn block_number(&self) -> u64 {
+            0
+        }
+    }
+
+    #[test]
+    fn rejects_operation_whose_fees_do_not_clear_the_gas_oracle_floor() {
+        let gas_oracle = Arc::new(GasOracle::new(FlatFloorProvider {
+            gas_price: U256::from(100),
+        }));
+        let ingress = GossipIngress::new(
+            UoPoolValidator::new(gas_oracle),
+            ReputationGossipThrottle::new(),
+        );
+
+        let results = ingress.ingest(
+            "peer-a",
+            Address::from_low_u64_be(9),
+            1,
+            vec![valid_uo()], // max_fee_per_gas/max_priority_fee_per_gas are both zero
+        );
+        assert!(matches!(
+            results[0],
+            Err(GossipIngressError::FailedValidation(_))
+        ));
+    }
+
+    fn uo_with_nonce(nonce: u64) -> UserOperation {
+        UserOperation::V06(UserOperationV06 {
+            sender: Address::from_low_u64_be(1),
+            nonce: U256::from(nonce),
+            init_code: Bytes::default(),
+            call_data: Bytes::default(),
+            call_gas_limit: U256::from(1),
+            verification_gas_limit: U256::from(1),
+            pre_verification_gas: U256::zero(),
+            max_fee_per_gas: U256::zero(),
+            max_priority_fee_per_gas: U256::zero(),
+            paymaster_and_data: Bytes::default(),
+            signature: Bytes::default(),
+        })
+    }
+
+    #[test]
+    fn raising_reputation_through_the_ingress_handle_lifts_an_active_throttle() {
+        let ingress = GossipIngress::new(no_floor_validator(), ReputationGossipThrottle::new());
+        let ep = Address::from_low_u64_be(9);
+
+        ingress.throttle().set_reputation("peer", -10);
+        let first = ingress.ingest("peer", ep, 1, vec![uo_with_nonce(1)]);
+        assert!(first[0].is_ok());
+
+        let second = ingress.ingest("peer", ep, 1, vec![uo_with_nonce(2)]);
+        assert!(matches!(
+            second[0],
+            Err(GossipIngressError::PeerThrottled(_))
+        ));
+
+        // A fresh reputation reading for this peer (e.g. pushed in by the node's reputation
+        // store) immediately raises its budget, lifting the throttle within the same window.
+        ingress.throttle().set_reputation("peer", 10);
+        let third = ingress.ingest("peer", ep, 1, vec![uo_with_nonce(3)]);
+        assert!(third[0].is_ok());
+    }
+}
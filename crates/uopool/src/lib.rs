@@ -0,0 +1,4 @@
+pub mod gas_oracle;
+pub mod gossip;
+pub mod reputation;
+pub mod state_snapshot;
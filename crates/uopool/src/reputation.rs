@@ -0,0 +1,76 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use ethers::types::Address;
+use silius_primitives::user_operation::EntryPointVersion;
+
+/// Reputation entries are keyed by `(entry_point, version)` rather than just `entry_point`, so
+/// entries recorded for the same address under v0.6 and under v0.7 never share a reputation
+/// bucket.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReputationScope {
+    pub entry_point: Address,
+    pub version: EntryPointVersion,
+}
+
+/// Per-entity reputation, scoped per [ReputationScope].
+#[derive(Default)]
+pub struct ReputationStore {
+    entries: RwLock<HashMap<ReputationScope, HashMap<Address, i64>>>,
+}
+
+impl ReputationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&self, scope: ReputationScope, entity: Address, value: i64) {
+        self.entries
+            .write()
+            .expect("lock poisoned")
+            .entry(scope)
+            .or_default()
+            .insert(entity, value);
+    }
+
+    pub fn get_all(&self, scope: ReputationScope) -> Vec<(Address, i64)> {
+        self.entries
+            .read()
+            .expect("lock poisoned")
+            .get(&scope)
+            .map(|m| m.iter().map(|(addr, v)| (*addr, *v)).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reputation_does_not_leak_across_versions_at_the_same_address() {
+        let store = ReputationStore::new();
+        let ep = Address::from_low_u64_be(1);
+        let entity = Address::from_low_u64_be(2);
+
+        store.set(
+            ReputationScope {
+                entry_point: ep,
+                version: EntryPointVersion::V06,
+            },
+            entity,
+            10,
+        );
+
+        let v07_entries = store.get_all(ReputationScope {
+            entry_point: ep,
+            version: EntryPointVersion::V07,
+        });
+        assert!(v07_entries.is_empty());
+
+        let v06_entries = store.get_all(ReputationScope {
+            entry_point: ep,
+            version: EntryPointVersion::V06,
+        });
+        assert_eq!(v06_entries, vec![(entity, 10)]);
+    }
+}
@@ -0,0 +1,222 @@
+use ethers::types::Address;
+use silius_primitives::{
+    p2p::UserOperationValidator,
+    state::{ReputationRecord, StakeInfoRecord, StateSnapshot, STATE_SNAPSHOT_VERSION},
+    user_operation::EntryPointVersion,
+    UserOperation,
+};
+
+use crate::reputation::{ReputationScope, ReputationStore};
+
+/// Builds a [StateSnapshot] from the current mempool contents, reputation store, and stake-info
+/// cache for one entry point.
+pub fn export_state(
+    entry_point: Address,
+    chain_id: u64,
+    version: EntryPointVersion,
+    user_operations: Vec<UserOperation>,
+    reputation_store: &ReputationStore,
+    stake_info: Vec<StakeInfoRecord>,
+) -> StateSnapshot {
+    let reputation = reputation_store
+        .get_all(ReputationScope {
+            entry_point,
+            version,
+        })
+        .into_iter()
+        .map(|(entity, value)| ReputationRecord { entity, value })
+        .collect();
+
+    StateSnapshot {
+        version: STATE_SNAPSHOT_VERSION,
+        entry_point,
+        chain_id,
+        user_operations,
+        reputation,
+        stake_info,
+    }
+}
+
+/// Why a [StateSnapshot] was rejected outright, before any of it was admitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StateImportError {
+    /// The snapshot's version, entry point, or chain id doesn't match this node's.
+    Mismatched,
+}
+
+/// Outcome of importing a [StateSnapshot]: which operations were admitted vs. rejected by
+/// re-validation, and how many reputation entries were merged in.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub admitted_user_operations: Vec<UserOperation>,
+    pub rejected_user_operations: Vec<(UserOperation, String)>,
+    pub reputation_entries_merged: usize,
+}
+
+/// Imports a [StateSnapshot]: rejects it outright if its entry point/chain id/version guard
+/// doesn't match, otherwise re-validates every UserOperation through `validator` (never trusting
+/// the exporting peer) and merges reputation into `reputation_store` rather than overwriting it,
+/// so a warm node can be primed without clobbering reputation it has already built up locally.
+pub fn import_state(
+    snapshot: StateSnapshot,
+    entry_point: Address,
+    chain_id: u64,
+    version: EntryPointVersion,
+    validator: &impl UserOperationValidator,
+    reputation_store: &ReputationStore,
+) -> Result<ImportSummary, StateImportError> {
+    if !snapshot.guard_matches(entry_point, chain_id) {
+        return Err(StateImportError::Mismatched);
+    }
+
+    let mut summary = ImportSummary::default();
+
+    for uo in snapshot.user_operations {
+        match validator.validate_user_operation(&uo, entry_point) {
+            Ok(()) => summary.admitted_user_operations.push(uo),
+            Err(reason) => summary.rejected_user_operations.push((uo, reason)),
+        }
+    }
+
+    let scope = ReputationScope {
+        entry_point,
+        version,
+    };
+    let existing: std::collections::HashMap<_, _> =
+        reputation_store.get_all(scope).into_iter().collect();
+
+    for entry in snapshot.reputation {
+        // Merge rather than overwrite: an entity already known locally keeps the higher of the
+        // two values instead of being clobbered by a potentially stale import.
+        let merged = match existing.get(&entry.entity) {
+            Some(&current) => current.max(entry.value),
+            None => entry.value,
+        };
+        reputation_store.set(scope, entry.entity, merged);
+        summary.reputation_entries_merged += 1;
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Bytes, U256};
+    use silius_primitives::user_operation::UserOperationV06;
+
+    struct AlwaysValid;
+    impl UserOperationValidator for AlwaysValid {
+        fn validate_user_operation(&self, _uo: &UserOperation, _ep: Address) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    struct AlwaysInvalid;
+    impl UserOperationValidator for AlwaysInvalid {
+        fn validate_user_operation(&self, _uo: &UserOperation, _ep: Address) -> Result<(), String> {
+            Err("nope".to_string())
+        }
+    }
+
+    fn sample_uo() -> UserOperation {
+        UserOperation::V06(UserOperationV06 {
+            sender: Address::from_low_u64_be(1),
+            nonce: U256::zero(),
+            init_code: Bytes::default(),
+            call_data: Bytes::default(),
+            call_gas_limit: U256::zero(),
+            verification_gas_limit: U256::zero(),
+            pre_verification_gas: U256::zero(),
+            max_fee_per_gas: U256::zero(),
+            max_priority_fee_per_gas: U256::zero(),
+            paymaster_and_data: Bytes::default(),
+            signature: Bytes::default(),
+        })
+    }
+
+    fn snapshot(entry_point: Address, chain_id: u64) -> StateSnapshot {
+        StateSnapshot {
+            version: STATE_SNAPSHOT_VERSION,
+            entry_point,
+            chain_id,
+            user_operations: vec![sample_uo()],
+            reputation: vec![ReputationRecord {
+                entity: Address::from_low_u64_be(2),
+                value: 5,
+            }],
+            stake_info: vec![],
+        }
+    }
+
+    #[test]
+    fn rejects_mismatched_entry_point() {
+        let ep = Address::from_low_u64_be(1);
+        let other_ep = Address::from_low_u64_be(9);
+        let store = ReputationStore::new();
+
+        let result = import_state(
+            snapshot(ep, 1),
+            other_ep,
+            1,
+            EntryPointVersion::V06,
+            &AlwaysValid,
+            &store,
+        );
+
+        assert_eq!(result, Err(StateImportError::Mismatched));
+    }
+
+    #[test]
+    fn rejects_mismatched_chain_id() {
+        let ep = Address::from_low_u64_be(1);
+        let store = ReputationStore::new();
+
+        let result = import_state(
+            snapshot(ep, 1),
+            ep,
+            999,
+            EntryPointVersion::V06,
+            &AlwaysValid,
+            &store,
+        );
+
+        assert_eq!(result, Err(StateImportError::Mismatched));
+    }
+
+    #[test]
+    fn revalidates_every_operation() {
+        let ep = Address::from_low_u64_be(1);
+        let store = ReputationStore::new();
+
+        let summary =
+            import_state(snapshot(ep, 1), ep, 1, EntryPointVersion::V06, &AlwaysInvalid, &store)
+                .unwrap();
+
+        assert!(summary.admitted_user_operations.is_empty());
+        assert_eq!(summary.rejected_user_operations.len(), 1);
+    }
+
+    #[test]
+    fn merges_reputation_instead_of_overwriting() {
+        let ep = Address::from_low_u64_be(1);
+        let entity = Address::from_low_u64_be(2);
+        let store = ReputationStore::new();
+        store.set(
+            ReputationScope {
+                entry_point: ep,
+                version: EntryPointVersion::V06,
+            },
+            entity,
+            50,
+        );
+
+        import_state(snapshot(ep, 1), ep, 1, EntryPointVersion::V06, &AlwaysValid, &store).unwrap();
+
+        let entries = store.get_all(ReputationScope {
+            entry_point: ep,
+            version: EntryPointVersion::V06,
+        });
+        assert_eq!(entries, vec![(entity, 50)]);
+    }
+}